@@ -6,6 +6,8 @@ pub enum IcType {
     Rectangles,
     SmoothNoise,
     GradientMix,
+    BallIndicators,
+    HatBlobs,
 }
 
 impl IcType {
@@ -15,6 +17,8 @@ impl IcType {
             IcType::Rectangles => "rectangles",
             IcType::SmoothNoise => "smooth_noise",
             IcType::GradientMix => "gradient_mix",
+            IcType::BallIndicators => "ball_indicators",
+            IcType::HatBlobs => "hat_blobs",
         }
     }
 }
@@ -22,11 +26,13 @@ impl IcType {
 pub fn sample_ic_type<R: Rng>(rng: &mut R) -> IcType {
     // You can adjust proportions later.
     // Keep it simple and fairly uniform for v1.
-    match rng.gen_range(0..4) {
+    match rng.gen_range(0..6) {
         0 => IcType::Gaussians,
         1 => IcType::Rectangles,
         2 => IcType::SmoothNoise,
-        _ => IcType::GradientMix,
+        3 => IcType::GradientMix,
+        4 => IcType::BallIndicators,
+        _ => IcType::HatBlobs,
     }
 }
 
@@ -113,6 +119,52 @@ pub fn generate_ic<R: Rng>(rng: &mut R, n: usize, ic: IcType) -> Vec<f32> {
                 }
             }
         }
+
+        IcType::BallIndicators => {
+            let balls = rng.gen_range(1..=3);
+            for _ in 0..balls {
+                let cx = rng.gen_range(0.15..0.85) * (n as f32 - 1.0);
+                let cy = rng.gen_range(0.15..0.85) * (n as f32 - 1.0);
+                let rho_max = ((n as f32) / 4.0).max(3.1);
+                let rho = rng.gen_range(3.0..rho_max);
+                let rim = rng.gen_range(0.5..1.5).min(rho);
+                let val = rng.gen_range(0.6..1.0);
+
+                let inner = rho - rim;
+                let outer = rho + rim;
+
+                for y in 0..n {
+                    for x in 0..n {
+                        let dx = x as f32 - cx;
+                        let dy = y as f32 - cy;
+                        let r = (dx * dx + dy * dy).sqrt();
+
+                        let indicator = if r <= inner {
+                            1.0
+                        } else if r >= outer {
+                            0.0
+                        } else {
+                            let t = (r - inner) / (outer - inner);
+                            0.5 * (1.0 + (std::f32::consts::PI * t).cos())
+                        };
+
+                        f[y * n + x] = f[y * n + x].max(val * indicator);
+                    }
+                }
+            }
+        }
+
+        IcType::HatBlobs => {
+            let impulses = rng.gen_range(2..=6);
+            for _ in 0..impulses {
+                let x = rng.gen_range(1..n - 1);
+                let y = rng.gen_range(1..n - 1);
+                let amp = rng.gen_range(0.5..1.0);
+                f[y * n + x] += amp;
+            }
+            let passes = rng.gen_range(3..=8);
+            f = hat_blur(&f, n, passes);
+        }
     }
 
     // normalize to [0,1]
@@ -154,3 +206,35 @@ fn box_blur(src: &[f32], n: usize, passes: usize) -> Vec<f32> {
     }
     cur
 }
+
+// Separable triangular ("hat") blur: [1,2,1]/4 along x, then along y.
+// Cheaper per pass than box_blur; from point impulses it produces sharply
+// peaked bumps with compact support (binomial profiles, piecewise-linear
+// only after a single pass; each pass widens the support by one cell per side).
+fn hat_blur(src: &[f32], n: usize, passes: usize) -> Vec<f32> {
+    let mut cur = src.to_vec();
+    let mut tmp = vec![0.0f32; n * n];
+
+    for _ in 0..passes {
+        for y in 0..n {
+            for x in 0..n {
+                let l = if x == 0 { cur[y * n + x] } else { cur[y * n + x - 1] };
+                let c = cur[y * n + x];
+                let r = if x == n - 1 { cur[y * n + x] } else { cur[y * n + x + 1] };
+                tmp[y * n + x] = (l + 2.0 * c + r) / 4.0;
+            }
+        }
+        std::mem::swap(&mut cur, &mut tmp);
+
+        for y in 0..n {
+            for x in 0..n {
+                let u = if y == 0 { cur[y * n + x] } else { cur[(y - 1) * n + x] };
+                let c = cur[y * n + x];
+                let d = if y == n - 1 { cur[y * n + x] } else { cur[(y + 1) * n + x] };
+                tmp[y * n + x] = (u + 2.0 * c + d) / 4.0;
+            }
+        }
+        std::mem::swap(&mut cur, &mut tmp);
+    }
+    cur
+}
@@ -5,8 +5,10 @@ use ic::{generate_ic, sample_ic_type};
 use rand::seq::SliceRandom;
 use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha8Rng;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use serde::Serialize;
-use solver_core::SolverCore;
+use solver_core::{error, BoundaryCondition, SolverCore};
 use std::fs::{self, File, OpenOptions};
 use std::io::{BufWriter, Write};
 use std::path::PathBuf;
@@ -50,15 +52,65 @@ struct Args {
     #[arg(long, default_value = "2,5,10,20")]
     mu_set: String,
 
+    /// Comma-separated boundary-condition set, sampled per-trajectory
+    /// (subset of "dirichlet,neumann,periodic")
+    #[arg(long, default_value = "dirichlet")]
+    bc: String,
+
     /// Reference safety factor s_ref (labels)
     #[arg(long, default_value_t = 0.4)]
     s_ref: f32,
 
+    /// Stepping backend used to advance the reference (target) rollout:
+    /// "explicit" (CFL-limited substeps via step_tau_ref), "adi"
+    /// (unconditionally stable Peaceman-Rachford splitting), or "spectral"
+    /// (exact DST solve). "adi" and "spectral" require --bc dirichlet.
+    #[arg(long, default_value = "explicit")]
+    step_backend: String,
+
+    /// Number of ADI half-step pairs per tau when --step-backend=adi
+    /// (ADI is unconditionally stable, so this is a quality/cost knob,
+    /// not a CFL requirement)
+    #[arg(long, default_value_t = 1)]
+    adi_steps: u32,
+
     /// Base RNG seed (reproducibility)
     #[arg(long, default_value_t = 123)]
     seed: u64,
 }
 
+#[derive(Clone, Copy, Debug)]
+enum StepBackend {
+    Explicit,
+    Adi,
+    Spectral,
+}
+
+impl StepBackend {
+    fn as_str(&self) -> &'static str {
+        match self {
+            StepBackend::Explicit => "explicit",
+            StepBackend::Adi => "adi",
+            StepBackend::Spectral => "spectral",
+        }
+    }
+
+    // Whether this backend is derived for homogeneous Dirichlet BC only
+    // (mirrors the guards on step_tau_adi/step_tau_spectral in solver-core).
+    fn dirichlet_only(&self) -> bool {
+        matches!(self, StepBackend::Adi | StepBackend::Spectral)
+    }
+}
+
+fn parse_step_backend(s: &str) -> Result<StepBackend, Box<dyn std::error::Error>> {
+    match s.trim().to_lowercase().as_str() {
+        "explicit" => Ok(StepBackend::Explicit),
+        "adi" => Ok(StepBackend::Adi),
+        "spectral" => Ok(StepBackend::Spectral),
+        other => Err(format!("unknown step backend: {other}").into()),
+    }
+}
+
 #[derive(Serialize)]
 struct MetaRow {
     global_sample_idx: u64,
@@ -78,9 +130,25 @@ struct MetaRow {
     tau: f32,
 
     s_ref: f32,
+    step_backend: String,
     k_used_ref: u32,
+    k_used_run: u32,
+
+    err_l2: f32,
+    err_linf: f32,
+    err_mass: f32,
 
     ic_type: String,
+    bc: String,
+}
+
+// One (u^t -> u^{t+tau}) sample, fully materialized so trajectories can be
+// generated out of order (e.g. across rayon workers) and then written back
+// in global_idx order to keep byte offsets reproducible.
+struct SampleRecord {
+    input: Vec<u8>,
+    target: Vec<u8>,
+    meta: MetaRow,
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -89,12 +157,30 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     if args.alpha_max <= args.alpha_min {
         return Err("alpha_max must be > alpha_min".into());
     }
+    if args.n < 3 {
+        return Err("n must be >= 3".into());
+    }
 
     let mu_values = parse_mu_set(&args.mu_set)?;
     if mu_values.is_empty() {
         return Err("mu_set parsed to empty set".into());
     }
 
+    let bc_values = parse_bc_set(&args.bc)?;
+    if bc_values.is_empty() {
+        return Err("bc parsed to empty set".into());
+    }
+
+    let step_backend = parse_step_backend(&args.step_backend)?;
+    if step_backend.dirichlet_only() && bc_values.iter().any(|&bc| bc != BoundaryCondition::Dirichlet)
+    {
+        return Err(format!(
+            "--step-backend {} is derived for homogeneous Dirichlet BC only; --bc must be \"dirichlet\"",
+            step_backend.as_str()
+        )
+        .into());
+    }
+
     fs::create_dir_all(&args.out)?;
 
     let mut input_writer = BufWriter::new(File::create(args.out.join("input.bin"))?);
@@ -108,59 +194,140 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             .open(args.out.join("meta.jsonl"))?,
     );
 
-    let mut global_idx: u64 = 0;
-
-    for local_traj in 0..args.traj_count {
-        let traj_idx = args.traj_start + local_traj;
-
-        // Deterministic per-trajectory seed
-        // (stable split-by-range; reproducible even if you regenerate)
-        let traj_seed = args.seed ^ ((traj_idx as u64).wrapping_mul(0x9E3779B97F4A7C15));
-        let mut rng = ChaCha8Rng::seed_from_u64(traj_seed);
+    #[cfg(feature = "parallel")]
+    let records: Vec<SampleRecord> = (0..args.traj_count)
+        .into_par_iter()
+        .flat_map(|local_traj| {
+            generate_trajectory(&args, &mu_values, &bc_values, step_backend, local_traj)
+        })
+        .collect();
+
+    #[cfg(not(feature = "parallel"))]
+    let records: Vec<SampleRecord> = (0..args.traj_count)
+        .flat_map(|local_traj| {
+            generate_trajectory(&args, &mu_values, &bc_values, step_backend, local_traj)
+        })
+        .collect();
+
+    for record in &records {
+        input_writer.write_all(&record.input)?;
+        target_writer.write_all(&record.target)?;
+        serde_json::to_writer(&mut meta_file, &record.meta)?;
+        meta_file.write_all(b"\n")?;
+    }
 
-        // Sample alpha per trajectory (cleaner than per-step)
-        let alpha = rng.gen_range(args.alpha_min..args.alpha_max);
+    input_writer.flush()?;
+    target_writer.flush()?;
+    meta_file.flush()?;
 
-        // Sample IC type + generate IC field
-        let ic_t = sample_ic_type(&mut rng);
-        let ic_field = generate_ic(&mut rng, args.n, ic_t);
+    println!("Wrote dataset to: {}", args.out.display());
+    println!(
+        "Samples: {} (traj_count={} * t_steps={})",
+        records.len(),
+        args.traj_count,
+        args.t_steps
+    );
 
-        // Create solver
-        let mut s = SolverCore::new(args.n).map_err(|e| format!("SolverCore::new: {e}"))?;
-        s.set_alpha(alpha);
-        s.set_s_ref(args.s_ref);
+    Ok(())
+}
 
-        // Apply IC into solver
+// Generates every sample for one trajectory. Each worker owns its own
+// SolverCore and RNG, seeded deterministically from traj_seed, so the
+// output is bit-identical whether this runs serially or across rayon
+// workers (the `parallel` feature only changes scheduling, not the seeds).
+fn generate_trajectory(
+    args: &Args,
+    mu_values: &[f32],
+    bc_values: &[BoundaryCondition],
+    step_backend: StepBackend,
+    local_traj: usize,
+) -> Vec<SampleRecord> {
+    let traj_idx = args.traj_start + local_traj;
+
+    // Deterministic per-trajectory seed
+    // (stable split-by-range; reproducible even if you regenerate)
+    let traj_seed = args.seed ^ ((traj_idx as u64).wrapping_mul(0x9E3779B97F4A7C15));
+    let mut rng = ChaCha8Rng::seed_from_u64(traj_seed);
+
+    // Sample alpha per trajectory (cleaner than per-step)
+    let alpha = rng.gen_range(args.alpha_min..args.alpha_max);
+
+    // Sample boundary condition per trajectory (same spirit as alpha: one
+    // physical regime per trajectory, not jittered every step)
+    let bc = *bc_values.choose(&mut rng).unwrap();
+
+    // Sample IC type + generate IC field
+    let ic_t = sample_ic_type(&mut rng);
+    let ic_field = generate_ic(&mut rng, args.n, ic_t);
+
+    // Create solver (args.n already validated >= 3 in main)
+    let mut s = SolverCore::new(args.n).expect("SolverCore::new: n already validated");
+    s.set_alpha(alpha);
+    s.set_s_ref(args.s_ref);
+    s.set_bc(bc);
+
+    // Apply IC into solver
+    for y in 0..args.n {
+        for x in 0..args.n {
+            s.set_cell(x, y, ic_field[y * args.n + x]);
+        }
+    }
+    s.finalize_ic();
+
+    let dx_val = s.get_dx();
+
+    // Scratch solver reused across every step of this trajectory to probe
+    // the cheaper run stepping from the same input as the reference target,
+    // without reallocating a fresh SolverCore (and its n*n buffers) per
+    // sample; only its field is overwritten each step.
+    let mut run_probe = SolverCore::new(args.n).expect("SolverCore::new: n already validated");
+    run_probe.set_alpha(alpha);
+    run_probe.set_bc(bc);
+
+    // Roll forward and collect pairs
+    let mut records = Vec::with_capacity(args.t_steps);
+    for step_idx in 0..args.t_steps {
+        // Sample mu per step
+        let mu = *mu_values.choose(&mut rng).unwrap();
+        s.set_mu(mu);
+
+        // Input
+        let u_in = s.clone_field();
+
+        // Advance by tau using the selected backend (this is the training target)
+        let (k_used_ref, tau_val) = match step_backend {
+            StepBackend::Explicit => s.step_tau_ref(),
+            StepBackend::Adi => s.step_tau_adi(args.adi_steps),
+            StepBackend::Spectral => s.step_tau_spectral(),
+        };
+
+        // Target
+        let u_out = s.clone_field();
+
+        // Roll the same input forward with the cheaper run stepping, purely
+        // to quantify how wrong it would have been vs. the reference target
+        run_probe.set_mu(mu);
         for y in 0..args.n {
             for x in 0..args.n {
-                s.set_cell(x, y, ic_field[y * args.n + x]);
+                run_probe.set_cell(x, y, u_in[y * args.n + x]);
             }
         }
-        s.finalize_ic();
-
-        let dx_val = s.get_dx();
-
-        // Roll forward and collect pairs
-        for step_idx in 0..args.t_steps {
-            // Sample mu per step
-            let mu = *mu_values.choose(&mut rng).unwrap();
-            s.set_mu(mu);
-
-            // Input
-            let u_in = s.clone_field();
-
-            // Advance by tau using reference stepping
-            let (k_used, tau_val) = s.step_tau_ref();
-
-            // Target
-            let u_out = s.clone_field();
-
-            // Write binaries
-            write_f32_vec(&mut input_writer, &u_in)?;
-            write_f32_vec(&mut target_writer, &u_out)?;
-
-            // Write metadata row (JSONL)
-            let row = MetaRow {
+        run_probe.finalize_ic();
+        let (k_used_run, _) = run_probe.step_tau_run();
+        let u_run = run_probe.clone_field();
+
+        let err_l2 = error::l2(&u_run, &u_out, args.n);
+        let err_linf = error::linf(&u_run, &u_out, args.n);
+        let err_mass = error::mass_delta(&u_run, &u_out);
+
+        // global_idx depends only on traj/step position since every
+        // trajectory produces the same number of samples (t_steps)
+        let global_idx = (local_traj * args.t_steps + step_idx) as u64;
+
+        records.push(SampleRecord {
+            input: f32_vec_to_bytes(&u_in),
+            target: f32_vec_to_bytes(&u_out),
+            meta: MetaRow {
                 global_sample_idx: global_idx,
                 split: args.split.clone(),
 
@@ -178,36 +345,29 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 tau: tau_val,
 
                 s_ref: args.s_ref,
-                k_used_ref: k_used,
+                step_backend: step_backend.as_str().to_string(),
+                k_used_ref,
+                k_used_run,
 
-                ic_type: ic_t.as_str().to_string(),
-            };
-
-            serde_json::to_writer(&mut meta_file, &row)?;
-            meta_file.write_all(b"\n")?;
+                err_l2,
+                err_linf,
+                err_mass,
 
-            global_idx += 1;
-        }
+                ic_type: ic_t.as_str().to_string(),
+                bc: bc.as_str().to_string(),
+            },
+        });
     }
 
-    input_writer.flush()?;
-    target_writer.flush()?;
-    meta_file.flush()?;
-
-    println!("Wrote dataset to: {}", args.out.display());
-    println!(
-        "Samples: {} (traj_count={} * t_steps={})",
-        global_idx, args.traj_count, args.t_steps
-    );
-
-    Ok(())
+    records
 }
 
-fn write_f32_vec<W: Write>(w: &mut W, v: &[f32]) -> std::io::Result<()> {
+fn f32_vec_to_bytes(v: &[f32]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(v.len() * 4);
     for &x in v {
-        w.write_all(&x.to_le_bytes())?;
+        out.extend_from_slice(&x.to_le_bytes());
     }
-    Ok(())
+    out
 }
 
 fn parse_mu_set(s: &str) -> Result<Vec<f32>, Box<dyn std::error::Error>> {
@@ -228,3 +388,21 @@ fn parse_mu_set(s: &str) -> Result<Vec<f32>, Box<dyn std::error::Error>> {
     out.dedup();
     Ok(out)
 }
+
+fn parse_bc_set(s: &str) -> Result<Vec<BoundaryCondition>, Box<dyn std::error::Error>> {
+    let mut out = Vec::new();
+    for part in s.split(',') {
+        let p = part.trim().to_lowercase();
+        if p.is_empty() {
+            continue;
+        }
+        let bc = match p.as_str() {
+            "dirichlet" => BoundaryCondition::Dirichlet,
+            "neumann" => BoundaryCondition::Neumann,
+            "periodic" => BoundaryCondition::Periodic,
+            other => return Err(format!("unknown bc mode: {other}").into()),
+        };
+        out.push(bc);
+    }
+    Ok(out)
+}
@@ -0,0 +1,110 @@
+//! Error metrics comparing two fields of the same grid size, used to label
+//! how much a cheaper stepping backend (e.g. step_tau_run) diverges from an
+//! authoritative rollout (e.g. step_tau_ref) advanced from the same input.
+
+/// L2 error over the interior: sqrt(mean((a-b)^2)) excluding the boundary.
+pub fn l2(a: &[f32], b: &[f32], n: usize) -> f32 {
+    let mut sum = 0.0f64;
+    let mut count = 0u64;
+    for y in 1..(n - 1) {
+        for x in 1..(n - 1) {
+            let i = y * n + x;
+            let d = (a[i] - b[i]) as f64;
+            sum += d * d;
+            count += 1;
+        }
+    }
+    (sum / count as f64).sqrt() as f32
+}
+
+/// L-infinity error over the interior: max|a-b| excluding the boundary.
+pub fn linf(a: &[f32], b: &[f32], n: usize) -> f32 {
+    let mut worst = 0.0f32;
+    for y in 1..(n - 1) {
+        for x in 1..(n - 1) {
+            let i = y * n + x;
+            let d = (a[i] - b[i]).abs();
+            if d > worst {
+                worst = d;
+            }
+        }
+    }
+    worst
+}
+
+/// Mass-conservation delta over the full field: sum(a) - sum(b).
+pub fn mass_delta(a: &[f32], b: &[f32]) -> f32 {
+    let sum_a: f64 = a.iter().map(|&v| v as f64).sum();
+    let sum_b: f64 = b.iter().map(|&v| v as f64).sum();
+    (sum_a - sum_b) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SolverCore;
+    use std::f32::consts::PI;
+
+    const N: usize = 4;
+
+    #[rustfmt::skip]
+    const A: [f32; N * N] = [
+        0.0,  1.0,  2.0,  3.0,
+        4.0,  5.0,  6.0,  7.0,
+        8.0,  9.0,  10.0, 11.0,
+        12.0, 13.0, 14.0, 15.0,
+    ];
+
+    #[test]
+    fn l2_matches_hand_computed_interior() {
+        let b = [0.0f32; N * N];
+        // interior cells (indices 5, 6, 9, 10) hold values 5, 6, 9, 10
+        let expected = ((5.0f64.powi(2) + 6.0f64.powi(2) + 9.0f64.powi(2) + 10.0f64.powi(2)) / 4.0).sqrt() as f32;
+        assert!((l2(&A, &b, N) - expected).abs() < 1e-4);
+    }
+
+    #[test]
+    fn linf_matches_hand_computed_interior() {
+        let b = [0.0f32; N * N];
+        assert_eq!(linf(&A, &b, N), 10.0);
+    }
+
+    #[test]
+    fn mass_delta_matches_hand_computed_sums() {
+        let a = [1.0f32, 2.0, 3.0];
+        let b = [0.5f32, 0.5, 0.5];
+        assert!((mass_delta(&a, &b) - 4.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn run_and_ref_agree_on_slowly_diffusing_case() {
+        // A single low-frequency sine mode: diffusion only mildly damps it,
+        // so both steppers' truncation error (which scales with the
+        // excited eigenvalue, not the grid's max eigenvalue) stays tiny.
+        let n = 16;
+        let mut run_solver = SolverCore::new(n).unwrap();
+        let mut ref_solver = SolverCore::new(n).unwrap();
+
+        for s in [&mut run_solver, &mut ref_solver] {
+            s.set_alpha(0.05);
+            s.set_mu(0.5);
+            for y in 1..n - 1 {
+                for x in 1..n - 1 {
+                    let fx = (PI * x as f32 / (n - 1) as f32).sin();
+                    let fy = (PI * y as f32 / (n - 1) as f32).sin();
+                    s.set_cell(x, y, fx * fy);
+                }
+            }
+            s.finalize_ic();
+        }
+
+        run_solver.step_tau_run();
+        ref_solver.step_tau_ref();
+
+        let err = l2(run_solver.field(), ref_solver.field(), n);
+        assert!(
+            err < 1e-3,
+            "run/ref should closely agree for slow diffusion, got l2 = {err}"
+        );
+    }
+}
@@ -1,3 +1,27 @@
+use std::f32::consts::PI;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+pub mod error;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoundaryCondition {
+    Dirichlet,
+    Neumann,
+    Periodic,
+}
+
+impl BoundaryCondition {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BoundaryCondition::Dirichlet => "dirichlet",
+            BoundaryCondition::Neumann => "neumann",
+            BoundaryCondition::Periodic => "periodic",
+        }
+    }
+}
+
 pub struct SolverCore {
     n: usize,
     alpha: f32,
@@ -5,6 +29,7 @@ pub struct SolverCore {
     s_run: f32,
     s_ref: f32,
     dx: f32,
+    bc: BoundaryCondition,
     field: Vec<f32>,
     next: Vec<f32>,
 }
@@ -24,6 +49,7 @@ impl SolverCore {
             s_run: 0.8,
             s_ref: 0.35,
             dx,
+            bc: BoundaryCondition::Dirichlet,
             field: vec![0.0; size],
             next: vec![0.0; size],
         })
@@ -39,6 +65,14 @@ impl SolverCore {
         self.mu = mu.max(0.0);
     }
 
+    pub fn set_bc(&mut self, bc: BoundaryCondition) {
+        self.bc = bc;
+    }
+
+    pub fn get_bc(&self) -> BoundaryCondition {
+        self.bc
+    }
+
     pub fn set_s_run(&mut self, s: f32) {
         self.s_run = s.clamp(0.05, 0.99);
     }
@@ -62,7 +96,7 @@ impl SolverCore {
         }
         let idx = y * self.n + x;
         self.field[idx] = (self.field[idx] + value).clamp(0.0, 1.0);
-        self.apply_dirichlet_bc();
+        self.apply_bc();
     }
     pub fn set_s_ref(&mut self, s: f32) {
         self.s_ref = s.clamp(0.05, 0.99);
@@ -114,13 +148,138 @@ impl SolverCore {
         self.step_tau_with_s(self.s_ref)
     }
 
+    // ---- Exact spectral step (constant alpha, homogeneous Dirichlet BC) ----
+    //
+    // The interior eigenmodes of the discrete 5-point Laplacian with zero
+    // Dirichlet BC are the sine basis v_p[j] = sin(p*pi*j/(n-1)), with
+    // eigenvalue lambda_p = (4/dx^2)*sin^2(p*pi/(2(n-1))) (lambda_{p,q} =
+    // lambda_p + lambda_q in 2D). Transforming to that basis, decaying each
+    // mode by exp(-alpha*lambda*tau), and transforming back advances the
+    // field by exactly tau with no substeps and no CFL constraint.
+    //
+    // Returns (k_used, tau) for interface parity with the substepped
+    // variants; k_used is always 1 since this is a single exact solve.
+    pub fn step_tau_spectral(&mut self) -> (u32, f32) {
+        assert_eq!(
+            self.bc,
+            BoundaryCondition::Dirichlet,
+            "step_tau_spectral is derived for homogeneous Dirichlet BC only"
+        );
+
+        let n = self.n;
+        let tau = self.get_tau();
+        let m = n - 2;
+
+        if m == 0 {
+            self.apply_dirichlet_bc();
+            return (1, tau);
+        }
+
+        let mut coeffs = vec![0.0f32; m * m];
+        for y in 0..m {
+            for x in 0..m {
+                coeffs[y * m + x] = self.field[(y + 1) * n + (x + 1)];
+            }
+        }
+
+        dst2d_forward(&mut coeffs, m);
+
+        let dx2 = self.dx * self.dx;
+        let lambda = |p: usize| (4.0 / dx2) * (PI * p as f32 / (2.0 * (n - 1) as f32)).sin().powi(2);
+        for p in 1..=m {
+            let lambda_p = lambda(p);
+            for q in 1..=m {
+                let lambda_pq = lambda_p + lambda(q);
+                coeffs[(p - 1) * m + (q - 1)] *= (-self.alpha * lambda_pq * tau).exp();
+            }
+        }
+
+        dst2d_inverse(&mut coeffs, m, n);
+
+        for y in 0..m {
+            for x in 0..m {
+                self.field[(y + 1) * n + (x + 1)] = coeffs[y * m + x].clamp(0.0, 1.0);
+            }
+        }
+        self.apply_dirichlet_bc();
+
+        (1, tau)
+    }
+
+    // ---- Unconditionally stable ADI (Peaceman-Rachford) step ----
+    //
+    // Splits each dt into two half-steps: implicit in x / explicit in y,
+    // then implicit in y / explicit in x. Each half-step reduces to n-2
+    // independent tridiagonal solves (one per interior row or column) with diagonal
+    // 1+2r and off-diagonals -r, solved in O(n) via the Thomas algorithm.
+    // This is second-order accurate and has no CFL restriction, so tau can
+    // be crossed in as few as n_steps == 1 regardless of dt_max.
+    pub fn step_tau_adi(&mut self, n_steps: u32) -> (u32, f32) {
+        let tau = self.get_tau();
+        let n_steps = n_steps.max(1);
+        let dt = tau / n_steps as f32;
+
+        for _ in 0..n_steps {
+            self.adi_step(dt);
+        }
+
+        (n_steps, tau)
+    }
+
+    fn adi_step(&mut self, dt: f32) {
+        assert_eq!(
+            self.bc,
+            BoundaryCondition::Dirichlet,
+            "step_tau_adi is derived for homogeneous Dirichlet BC only (RHS boundary pinning assumes it)"
+        );
+
+        let n = self.n;
+        let dx2 = self.dx * self.dx;
+        let r = self.alpha * dt / (2.0 * dx2);
+
+        // Half-step 1: implicit in x, explicit in y.
+        let mut half = self.field.clone();
+        for y in 1..(n - 1) {
+            let row = y * n;
+            let mut rhs = vec![0.0f32; n];
+            for x in 1..(n - 1) {
+                let i = row + x;
+                let u = self.field[i];
+                let up = self.field[i - n];
+                let down = self.field[i + n];
+                rhs[x] = u + r * (up + down - 2.0 * u);
+            }
+            let sol = thomas_solve_interior(n, r, &rhs);
+            half[row + 1..row + n - 1].copy_from_slice(&sol[1..n - 1]);
+        }
+
+        // Half-step 2: implicit in y, explicit in x.
+        for x in 1..(n - 1) {
+            let mut rhs = vec![0.0f32; n];
+            for y in 1..(n - 1) {
+                let i = y * n + x;
+                let u = half[i];
+                let left = half[i - 1];
+                let right = half[i + 1];
+                rhs[y] = u + r * (left + right - 2.0 * u);
+            }
+            let sol = thomas_solve_interior(n, r, &rhs);
+            for y in 1..(n - 1) {
+                self.next[y * n + x] = sol[y];
+            }
+        }
+
+        self.swap_buffers();
+        self.apply_dirichlet_bc();
+    }
+
     pub fn set_cell(&mut self, x: usize, y: usize, value: f32) {
         if x >= self.n || y >= self.n { return; }
         let idx = y * self.n + x;
         self.field[idx] = value.clamp(0.0, 1.0);
     }
     pub fn finalize_ic(&mut self) {
-        self.apply_dirichlet_bc();
+        self.apply_bc();
     }
     pub fn clone_field(&self) -> Vec<f32> {
         self.field.clone()
@@ -136,24 +295,61 @@ impl SolverCore {
         let dx2 = self.dx * self.dx;
         let c = self.alpha * dt / dx2;
 
-        for y in 1..(n - 1) {
-            let row = y * n;
-            for x in 1..(n - 1) {
-                let i = row + x;
+        #[cfg(feature = "parallel")]
+        {
+            let field = &self.field;
+            self.next[n..n * (n - 1)]
+                .par_chunks_mut(n)
+                .enumerate()
+                .for_each(|(row_idx, row)| {
+                    let y = row_idx + 1;
+                    let base = y * n;
+                    for x in 1..(n - 1) {
+                        let i = base + x;
+                        let u = field[i];
+                        let up = field[i - n];
+                        let down = field[i + n];
+                        let left = field[i - 1];
+                        let right = field[i + 1];
 
-                let u = self.field[i];
-                let up = self.field[i - n];
-                let down = self.field[i + n];
-                let left = self.field[i - 1];
-                let right = self.field[i + 1];
+                        let lap = (up + down + left + right) - 4.0 * u;
+                        row[x] = (u + c * lap).clamp(0.0, 1.0);
+                    }
+                });
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            for y in 1..(n - 1) {
+                let row = y * n;
+                for x in 1..(n - 1) {
+                    let i = row + x;
+
+                    let u = self.field[i];
+                    let up = self.field[i - n];
+                    let down = self.field[i + n];
+                    let left = self.field[i - 1];
+                    let right = self.field[i + 1];
 
-                let lap = (up + down + left + right) - 4.0 * u;
-                self.next[i] = (u + c * lap).clamp(0.0, 1.0);
+                    let lap = (up + down + left + right) - 4.0 * u;
+                    self.next[i] = (u + c * lap).clamp(0.0, 1.0);
+                }
             }
         }
 
         self.swap_buffers();
-        self.apply_dirichlet_bc();
+        self.apply_bc();
+    }
+
+    // Dispatches to the configured BC. Used by explicit_step and IC setup;
+    // the spectral and ADI steppers are derived for homogeneous Dirichlet
+    // BC specifically and always call apply_dirichlet_bc directly.
+    fn apply_bc(&mut self) {
+        match self.bc {
+            BoundaryCondition::Dirichlet => self.apply_dirichlet_bc(),
+            BoundaryCondition::Neumann => self.apply_neumann_bc(),
+            BoundaryCondition::Periodic => self.apply_periodic_bc(),
+        }
     }
 
     fn apply_dirichlet_bc(&mut self) {
@@ -168,8 +364,228 @@ impl SolverCore {
         }
     }
 
+    // Zero-flux: mirror the nearest interior value into the boundary so
+    // the gradient vanishes there and total heat is conserved.
+    fn apply_neumann_bc(&mut self) {
+        let n = self.n;
+        for x in 0..n {
+            self.field[x] = self.field[n + x];
+            self.field[(n - 1) * n + x] = self.field[(n - 2) * n + x];
+        }
+        for y in 0..n {
+            self.field[y * n] = self.field[y * n + 1];
+            self.field[y * n + (n - 1)] = self.field[y * n + (n - 2)];
+        }
+    }
+
+    // Periodic over the n-2 interior cells: fill each boundary ghost cell
+    // with the interior value from the opposite edge (column 0 takes column
+    // n-2, column n-1 takes column 1; rows likewise), so the first interior
+    // cell's left-neighbor in the stencil is the last interior cell.
+    fn apply_periodic_bc(&mut self) {
+        let n = self.n;
+        for x in 0..n {
+            self.field[x] = self.field[(n - 2) * n + x];
+            self.field[(n - 1) * n + x] = self.field[n + x];
+        }
+        for y in 0..n {
+            self.field[y * n] = self.field[y * n + (n - 2)];
+            self.field[y * n + (n - 1)] = self.field[y * n + 1];
+        }
+    }
+
     fn swap_buffers(&mut self) {
         std::mem::swap(&mut self.field, &mut self.next);
         self.next.fill(0.0);
     }
 }
+
+// ---- Thomas algorithm for the ADI tridiagonal solves ----
+//
+// Solves (I - r*L)*x = rhs for the n-2 interior unknowns of a 1D line, with
+// diagonal 1+2r and off-diagonals -r. `rhs` is indexed like the full line
+// (length n); entries 0 and n-1 are the Dirichlet boundary and are ignored,
+// since those rows pin x[0] = x[n-1] = 0.
+fn thomas_solve_interior(n: usize, r: f32, rhs: &[f32]) -> Vec<f32> {
+    let m = n - 2;
+    let a = -r;
+    let b = 1.0 + 2.0 * r;
+
+    let mut cprime = vec![0.0f32; m];
+    let mut dprime = vec![0.0f32; m];
+
+    cprime[0] = a / b;
+    dprime[0] = rhs[1] / b;
+
+    for i in 1..m {
+        let denom = b - a * cprime[i - 1];
+        cprime[i] = a / denom;
+        dprime[i] = (rhs[i + 1] - a * dprime[i - 1]) / denom;
+    }
+
+    let mut sol = vec![0.0f32; n];
+    sol[m] = dprime[m - 1];
+    for i in (0..m - 1).rev() {
+        sol[i + 1] = dprime[i] - cprime[i] * sol[i + 2];
+    }
+    sol
+}
+
+// ---- Discrete sine transform (type I, size m) ----
+//
+// DST-I is its own inverse up to the 2/(m+1) normalization, so the same
+// 1D kernel is reused for both directions; callers apply the scale factor.
+fn dst1d(data: &mut [f32]) {
+    let m = data.len();
+    let denom = (m + 1) as f32;
+    let input = data.to_vec();
+    for p in 1..=m {
+        let mut sum = 0.0f32;
+        for j in 1..=m {
+            sum += input[j - 1] * (PI * p as f32 * j as f32 / denom).sin();
+        }
+        data[p - 1] = sum;
+    }
+}
+
+// 2D forward DST: 1D DST along every row, then along every column.
+fn dst2d_forward(coeffs: &mut [f32], m: usize) {
+    for row in coeffs.chunks_mut(m) {
+        dst1d(row);
+    }
+
+    let mut col = vec![0.0f32; m];
+    for x in 0..m {
+        for y in 0..m {
+            col[y] = coeffs[y * m + x];
+        }
+        dst1d(&mut col);
+        for y in 0..m {
+            coeffs[y * m + x] = col[y];
+        }
+    }
+}
+
+// 2D inverse DST: same kernel applied along columns then rows, each scaled
+// by 2/(n-1); the forward transform is unscaled, so this supplies the full
+// normalization.
+fn dst2d_inverse(coeffs: &mut [f32], m: usize, n: usize) {
+    let norm = 2.0 / ((n - 1) as f32);
+
+    let mut col = vec![0.0f32; m];
+    for x in 0..m {
+        for y in 0..m {
+            col[y] = coeffs[y * m + x];
+        }
+        dst1d(&mut col);
+        for y in 0..m {
+            coeffs[y * m + x] = col[y] * norm;
+        }
+    }
+
+    for row in coeffs.chunks_mut(m) {
+        dst1d(row);
+        for v in row.iter_mut() {
+            *v *= norm;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Fills the interior with a single sine eigenmode v_p[x]*v_q[y], the
+    // exact basis both step_tau_spectral and (to second order) step_tau_adi
+    // are derived in, so the post-step field has a closed form to check
+    // against: u(tau) = amp * v_p * v_q * exp(-alpha * lambda_{p,q} * tau).
+    fn set_sine_mode(s: &mut SolverCore, n: usize, p: usize, q: usize, amp: f32) {
+        for y in 0..n {
+            for x in 0..n {
+                let fx = (PI * p as f32 * x as f32 / (n - 1) as f32).sin();
+                let fy = (PI * q as f32 * y as f32 / (n - 1) as f32).sin();
+                s.set_cell(x, y, amp * fx * fy);
+            }
+        }
+        s.finalize_ic();
+    }
+
+    fn lambda_pq(dx: f32, n: usize, p: usize, q: usize) -> f32 {
+        let lambda = |k: usize| (4.0 / (dx * dx)) * (PI * k as f32 / (2.0 * (n - 1) as f32)).sin().powi(2);
+        lambda(p) + lambda(q)
+    }
+
+    #[test]
+    fn step_tau_spectral_matches_analytic_sine_decay() {
+        let n = 16;
+        let mut s = SolverCore::new(n).unwrap();
+        s.set_alpha(0.3);
+        s.set_mu(4.0);
+        set_sine_mode(&mut s, n, 1, 1, 0.7);
+
+        let dx = s.get_dx();
+        let tau = s.get_tau();
+        let decay = (-0.3 * lambda_pq(dx, n, 1, 1) * tau).exp();
+
+        s.step_tau_spectral();
+
+        for y in 1..n - 1 {
+            for x in 1..n - 1 {
+                let fx = (PI * x as f32 / (n - 1) as f32).sin();
+                let fy = (PI * y as f32 / (n - 1) as f32).sin();
+                let expected = 0.7 * fx * fy * decay;
+                let actual = s.field()[y * n + x];
+                assert!(
+                    (actual - expected).abs() < 1e-3,
+                    "mismatch at ({x},{y}): got {actual}, expected {expected}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn step_tau_adi_matches_analytic_sine_decay() {
+        let n = 16;
+        let mut s = SolverCore::new(n).unwrap();
+        s.set_alpha(0.3);
+        s.set_mu(4.0);
+        set_sine_mode(&mut s, n, 1, 1, 0.7);
+
+        let dx = s.get_dx();
+        let tau = s.get_tau();
+        let decay = (-0.3 * lambda_pq(dx, n, 1, 1) * tau).exp();
+
+        // A handful of half-step pairs keeps the splitting's second-order
+        // error well under the tolerance below.
+        s.step_tau_adi(8);
+
+        for y in 1..n - 1 {
+            for x in 1..n - 1 {
+                let fx = (PI * x as f32 / (n - 1) as f32).sin();
+                let fy = (PI * y as f32 / (n - 1) as f32).sin();
+                let expected = 0.7 * fx * fy * decay;
+                let actual = s.field()[y * n + x];
+                assert!(
+                    (actual - expected).abs() < 5e-3,
+                    "mismatch at ({x},{y}): got {actual}, expected {expected}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "homogeneous Dirichlet BC only")]
+    fn step_tau_spectral_rejects_non_dirichlet_bc() {
+        let mut s = SolverCore::new(8).unwrap();
+        s.set_bc(BoundaryCondition::Neumann);
+        s.step_tau_spectral();
+    }
+
+    #[test]
+    #[should_panic(expected = "homogeneous Dirichlet BC only")]
+    fn step_tau_adi_rejects_non_dirichlet_bc() {
+        let mut s = SolverCore::new(8).unwrap();
+        s.set_bc(BoundaryCondition::Periodic);
+        s.step_tau_adi(1);
+    }
+}